@@ -1,10 +1,14 @@
 #![warn(clippy::all, clippy::pedantic)]
 
 use bracket_lib::prelude::*;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
 
 enum GameMode {
     Menu,
     Playing,
+    Paused,
     End,
 }
 
@@ -12,7 +16,13 @@ const SCREEN_WIDTH: i32 = 40;
 const SCREEN_HEIGHT: i32 = 25;
 
 /// Game Speed
-const FRAME_DURATION: f32 = 50.0;
+const BASE_FRAME_DURATION: f32 = 50.0;
+const MIN_FRAME_DURATION: f32 = 20.0;
+
+/// Additional obstacle scroll speed added per point of score, on top of the
+/// player's own `+1`-per-tick advance that already scrolls obstacles at the
+/// original pace when `score == 0`.
+const OBSTACLE_SPEED_PER_SCORE: f32 = 0.05;
 
 /// Velocity Parameters
 const TERMINAL_VELOCITY: f32 = 1.0;
@@ -20,7 +30,7 @@ const DELTA_V: f32 = 0.1;
 const FLAP_DELTA_V: f32 = -0.5;
 
 // Graphic Glyphs
-const DRAGON_GLYPTH: i32 = 64;
+const DRAGON_FRAMES: [u16; 6] = [64, 1, 2, 3, 2, 1];
 const WALL_GLYPH: i32 = 179;
 const GROUND_GLPYH: i32 = 35;
 
@@ -28,10 +38,33 @@ const GROUND_GLPYH: i32 = 35;
 const GAP_Y_MIN: i32 = 5;
 const GAP_Y_MAX: i32 = 20;
 
+/// Flavor captions drawn in the gap of a passing obstacle.
+const LABELS: [&str; 5] = ["Boo!", "Flap!", "Gotcha!", "Wheee!", "Yikes!"];
+
+/// Horizontal gap maintained between queued obstacles.
+const OBSTACLE_SPACING: f32 = 20.0;
+/// Number of obstacles kept in the queue at once; at this spacing no more
+/// than two of them are on screen together.
+const NUM_OBSTACLES: i32 = 3;
+
+/// Axis-aligned bounding box, in tile coordinates.
+struct Rect {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+}
+
+/// Whether two axis-aligned rectangles overlap.
+fn collides(a: &Rect, b: &Rect) -> bool {
+    a.x0 < b.x1 && a.x1 > b.x0 && a.y0 < b.y1 && a.y1 > b.y0
+}
+
 struct Player {
     x: i32,
     y: f32,
     velocity: f32,
+    frame: usize,
 }
 
 impl Player {
@@ -40,6 +73,7 @@ impl Player {
             x,
             y: y as f32,
             velocity: 0.0,
+            frame: 0,
         }
     }
 
@@ -53,7 +87,7 @@ impl Player {
             PointF::new(2.0, 2.0),
             YELLOW,
             NAVY,
-            DRAGON_GLYPTH,
+            DRAGON_FRAMES[self.frame],
         );
         ctx.set_active_console(0);
     }
@@ -68,6 +102,9 @@ impl Player {
         self.y += self.velocity;
         self.x += 1;
 
+        // Advance the wing-flap animation.
+        self.frame = (self.frame + 1) % DRAGON_FRAMES.len();
+
         // Upper bound for vertical position.
         if self.y < 0.0 {
             self.y = 0.0;
@@ -76,30 +113,45 @@ impl Player {
 
     fn flap(&mut self) {
         self.velocity = FLAP_DELTA_V;
+        self.frame = 1;
+    }
+
+    /// Bounding rect of the 2x2-tile dragon sprite.
+    fn bounds(&self) -> Rect {
+        Rect {
+            x0: self.x as f32,
+            y0: self.y,
+            x1: self.x as f32 + 2.0,
+            y1: self.y + 2.0,
+        }
     }
 }
 
 struct Obstacle {
     /// World position
-    x: i32,
+    x: f32,
     /// Center of gap in wall
     gap_y: i32,
     /// Width of gap
     size: i32,
+    /// Flavor caption shown in the gap
+    label: &'static str,
 }
 
 impl Obstacle {
-    fn new(x: i32, score: i32) -> Self {
+    fn new(x: f32, score: i32) -> Self {
         let mut random = RandomNumberGenerator::new();
+        let label = LABELS[random.range(0, LABELS.len() as i32) as usize];
         Self {
             x,
             gap_y: random.range(GAP_Y_MIN, GAP_Y_MAX),
             size: i32::max(2, 20 - score),
+            label,
         }
     }
 
     fn render(&mut self, ctx: &mut BTerm, player_x: i32) {
-        let screen_x = self.x - player_x;
+        let screen_x = (self.x - player_x as f32).round() as i32;
         let half_size = self.size / 2;
 
         // Draw the top half of the obstacle
@@ -111,14 +163,30 @@ impl Obstacle {
         for y in self.gap_y + half_size..SCREEN_HEIGHT {
             ctx.set(screen_x, y, GRAY, NAVY, WALL_GLYPH)
         }
+
+        // Caption drawn in the gap, skipped when off-screen or too tight to fit.
+        if screen_x >= 0 && screen_x < SCREEN_WIDTH && self.size >= self.label.len() as i32 {
+            ctx.print_color(screen_x, self.gap_y, WHITE, NAVY, self.label);
+        }
     }
 
     fn hit_obstacle(&self, player: &Player) -> bool {
         let half_size = self.size / 2;
-        let does_x_match = player.x == self.x;
-        let player_above_gap = player.y < (self.gap_y - half_size) as f32;
-        let player_below_gap = player.y > (self.gap_y + half_size) as f32;
-        does_x_match && (player_above_gap || player_below_gap)
+        let top_wall = Rect {
+            x0: self.x,
+            y0: 0.0,
+            x1: self.x + 1.0,
+            y1: (self.gap_y - half_size) as f32,
+        };
+        let bottom_wall = Rect {
+            x0: self.x,
+            y0: (self.gap_y + half_size) as f32,
+            x1: self.x + 1.0,
+            y1: SCREEN_HEIGHT as f32,
+        };
+
+        let player_rect = player.bounds();
+        collides(&player_rect, &top_wall) || collides(&player_rect, &bottom_wall)
     }
 }
 
@@ -127,7 +195,9 @@ struct State {
     frame_time: f32,
     mode: GameMode,
     score: i32,
-    obstacle: Obstacle,
+    obstacles: VecDeque<Obstacle>,
+    high_score: i32,
+    new_best: bool,
 }
 
 impl State {
@@ -138,53 +208,105 @@ impl State {
             frame_time: 0.0,
             mode: GameMode::Menu,
             score: 0,
-            obstacle: Obstacle::new(SCREEN_WIDTH, 0),
+            obstacles: spawn_obstacles(SCREEN_WIDTH as f32, 0),
+            high_score: load_high_score(),
+            new_best: false,
         }
     }
 
-    fn play(&mut self, ctx: &mut BTerm) {
+    /// Draw the player, obstacles, HUD and ground — shared by `play` and
+    /// `paused` so the frozen pause screen matches the live one exactly.
+    fn render_world(&mut self, ctx: &mut BTerm) {
         ctx.cls_bg(NAVY);
+        self.player.render(ctx);
+
+        for obstacle in &mut self.obstacles {
+            obstacle.render(ctx, self.player.x);
+        }
+
+        ctx.print_color(0, 0, CYAN, NAVY, "Press SPACE to flap.");
+        ctx.print_color(0, 1, MAGENTA, NAVY, &format!("Score: {}", self.score));
+
+        render_land(ctx);
+    }
+
+    fn play(&mut self, ctx: &mut BTerm) {
+        if let Some(VirtualKeyCode::P) = ctx.key {
+            self.mode = GameMode::Paused;
+            return;
+        }
+
         self.frame_time += ctx.frame_time_ms;
-        if self.frame_time > FRAME_DURATION {
+        if self.frame_time > frame_duration_for_score(self.score) {
             self.frame_time = 0.0;
 
             self.player.gravity_and_move();
+
+            let speed = obstacle_speed_for_score(self.score);
+            for obstacle in &mut self.obstacles {
+                obstacle.x -= speed;
+            }
         }
 
         // If the space key has been pressed this frame, flap.
         if let Some(VirtualKeyCode::Space) = ctx.key {
             self.player.flap();
         }
-        self.player.render(ctx);
 
-        self.obstacle.render(ctx, self.player.x);
-        if self.player.x > self.obstacle.x {
-            self.score += 1;
-            self.obstacle = Obstacle::new(self.player.x + SCREEN_WIDTH, self.score);
+        self.render_world(ctx);
+
+        // Once the frontmost obstacle has been passed, award a point, drop
+        // it from the queue and push a fresh one to keep the pipeline full.
+        if let Some(front) = self.obstacles.front() {
+            if self.player.x as f32 > front.x {
+                self.score += 1;
+                self.obstacles.pop_front();
+                let last_x = self.obstacles.back().map_or(self.player.x as f32, |o| o.x);
+                self.obstacles
+                    .push_back(Obstacle::new(last_x + OBSTACLE_SPACING, self.score));
+            }
         }
-        // Print controls and score.
-        ctx.print_color(0, 0, CYAN, NAVY, "Press SPACE to flap.");
-        ctx.print_color(0, 1, MAGENTA, NAVY, &format!("Score: {}", self.score));
 
-        render_land(ctx);
+        let hit_obstacle = self.obstacles.iter().any(|o| o.hit_obstacle(&self.player));
 
         // SCREEN_HEIGHT - 1 to account for "ground"
-        if self.player.y as i32 > (SCREEN_HEIGHT - 1) || self.obstacle.hit_obstacle(&self.player) {
+        if self.player.y as i32 > (SCREEN_HEIGHT - 1) || hit_obstacle {
+            self.new_best = self.score > self.high_score;
+            if self.new_best {
+                self.high_score = self.score;
+                save_high_score(self.high_score);
+            }
             self.mode = GameMode::End;
         }
     }
 
+    fn paused(&mut self, ctx: &mut BTerm) {
+        self.render_world(ctx);
+
+        ctx.print_color_centered(12, YELLOW, NAVY, "PAUSED - press P to resume");
+
+        if let Some(VirtualKeyCode::P) = ctx.key {
+            self.resume();
+        }
+    }
+
+    fn resume(&mut self) {
+        self.mode = GameMode::Playing;
+    }
+
     fn restart(&mut self) {
         self.player = Player::new(5, SCREEN_HEIGHT / 2);
         self.frame_time = 0.0;
         self.mode = GameMode::Playing;
         self.score = 0;
-        self.obstacle = Obstacle::new(SCREEN_WIDTH, 0);
+        self.obstacles = spawn_obstacles(SCREEN_WIDTH as f32, 0);
+        self.new_best = false;
     }
 
     fn main_menu(&mut self, ctx: &mut BTerm) {
         ctx.cls();
         ctx.print_color_centered(5, YELLOW, BLACK, "Welcome to Flappy Dragon");
+        ctx.print_color_centered(7, GREEN, BLACK, &format!("Best: {}", self.high_score));
         ctx.print_color_centered(8, CYAN, BLACK, "(P) Play Game");
         ctx.print_color_centered(9, CYAN, BLACK, "(Q) Quit Game");
 
@@ -201,8 +323,12 @@ impl State {
         ctx.cls();
         ctx.print_color_centered(5, RED, BLACK, "You are dead!");
         ctx.print_centered(6, &format!("You earned {} points", self.score));
-        ctx.print_color_centered(8, CYAN, BLACK, "(P) Play Again");
-        ctx.print_color_centered(9, CYAN, BLACK, "(Q) Quit Game");
+        ctx.print_color_centered(7, GREEN, BLACK, &format!("Best: {}", self.high_score));
+        if self.new_best {
+            ctx.print_color_centered(8, YELLOW, BLACK, "NEW BEST!");
+        }
+        ctx.print_color_centered(10, CYAN, BLACK, "(P) Play Again");
+        ctx.print_color_centered(11, CYAN, BLACK, "(Q) Quit Game");
 
         if let Some(key) = ctx.key {
             match key {
@@ -214,6 +340,64 @@ impl State {
     }
 }
 
+/// The OS-appropriate per-user data directory, without pulling in a crate
+/// just for this: `%APPDATA%` on Windows, else XDG_DATA_HOME (falling back
+/// to `~/.local/share`) on Unix-likes.
+fn data_dir() -> Option<PathBuf> {
+    if let Some(appdata) = std::env::var_os("APPDATA") {
+        return Some(PathBuf::from(appdata));
+    }
+    if let Some(xdg_data_home) = std::env::var_os("XDG_DATA_HOME") {
+        return Some(PathBuf::from(xdg_data_home));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+}
+
+/// Where the persisted high score lives, in the OS-appropriate data directory.
+fn high_score_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("flappy-dragon").join("high_score.txt"))
+}
+
+/// Load the saved high score, defaulting to `0` if none is saved or readable.
+fn load_high_score() -> i32 {
+    high_score_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| contents.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Persist `score` as the new high score, ignoring failures to write.
+fn save_high_score(score: i32) {
+    let Some(path) = high_score_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        if fs::create_dir_all(parent).is_err() {
+            return;
+        }
+    }
+    let _ = fs::write(path, score.to_string());
+}
+
+/// Build an initial spread of obstacles, evenly spaced from `start_x`.
+fn spawn_obstacles(start_x: f32, score: i32) -> VecDeque<Obstacle> {
+    (0..NUM_OBSTACLES)
+        .map(|i| Obstacle::new(start_x + i as f32 * OBSTACLE_SPACING, score))
+        .collect()
+}
+
+/// Extra obstacle scroll speed layered on top of the player's own advance, so
+/// the combined rate matches the original pace at `score == 0` and only
+/// climbs from there.
+fn obstacle_speed_for_score(score: i32) -> f32 {
+    score as f32 * OBSTACLE_SPEED_PER_SCORE
+}
+
+/// Physics ticks fire more often as the score climbs, tightening reaction time.
+fn frame_duration_for_score(score: i32) -> f32 {
+    (BASE_FRAME_DURATION - score as f32 * 0.5).max(MIN_FRAME_DURATION)
+}
+
 fn render_land(ctx: &mut BTerm) {
     for x in 0..SCREEN_WIDTH {
         ctx.set(x, SCREEN_HEIGHT - 1, WHITE, NAVY, GROUND_GLPYH);
@@ -226,6 +410,7 @@ impl GameState for State {
             GameMode::Menu => self.main_menu(ctx),
             GameMode::End => self.dead(ctx),
             GameMode::Playing => self.play(ctx),
+            GameMode::Paused => self.paused(ctx),
         }
     }
 }